@@ -0,0 +1,68 @@
+use crate::collect::{collect_into_array_unchecked, try_collect_into_array};
+
+/// Build an array of length `N`, initializing each element using `f`
+///
+/// ```
+/// use unarray::*;
+/// let array: [usize; 5] = build_array(|i| i * 2);
+/// assert_eq!(array, [0, 2, 4, 6, 8]);
+/// ```
+/// `f` is called once for every index in `0..N`, in order, and its return value becomes the
+/// element at that index
+///
+/// For functions that can fail, see [`build_array_result`] and [`build_array_option`]
+pub fn build_array<T, const N: usize>(f: impl FnMut(usize) -> T) -> [T; N] {
+    // SAFETY: `0..N` always yields exactly `N` items, so `collect_into_array_unchecked`'s
+    // contract is upheld
+    unsafe { collect_into_array_unchecked((0..N).map(f)) }
+}
+
+/// Build an array of length `N`, short-circuiting if `f` produces an `Err` for any index
+///
+/// ```
+/// # use unarray::*;
+/// let array: Result<[usize; 3], ()> = build_array_result(|i| Ok(i * 2));
+/// assert_eq!(array, Ok([0, 2, 4]));
+/// ```
+/// This calls `f` once for every index in `0..N`, in order. If any call produces an `Err`, this
+/// function immediately returns that error. Otherwise, it returns `Ok(result)` where `result`
+/// contains the built elements in an array
+///
+/// This function does not allocate space on the heap
+///
+/// For functions that return an `Option`, consider using [`build_array_option`]
+pub fn build_array_result<T, E, const N: usize>(
+    f: impl FnMut(usize) -> Result<T, E>,
+) -> Result<[T; N], E> {
+    try_collect_into_array((0..N).map(f)).map(|array| {
+        // `0..N` always yields exactly `N` items, so `try_collect_into_array` can never report
+        // too few
+        array.expect("0..N always yields exactly N items")
+    })
+}
+
+/// Build an array of length `N`, short-circuiting if `f` produces a `None` for any index
+///
+/// ```
+/// # use unarray::*;
+/// let array: Option<[usize; 3]> = build_array_option(|i| Some(i * 2));
+/// assert_eq!(array, Some([0, 2, 4]));
+/// ```
+/// This calls `f` once for every index in `0..N`, in order. If any call produces `None`, this
+/// function immediately returns `None`. Otherwise, it returns `Some(result)` where `result`
+/// contains the built elements in an array
+///
+/// This function does not allocate space on the heap
+///
+/// For functions that return a `Result`, consider using [`build_array_result`]
+pub fn build_array_option<T, const N: usize>(
+    mut f: impl FnMut(usize) -> Option<T>,
+) -> Option<[T; N]> {
+    // transform to a `Result`-returning function so we can avoid duplicating short-circuit logic
+    let actual_f = |i: usize| -> Result<T, ()> { f(i).ok_or(()) };
+
+    match build_array_result(actual_f) {
+        Ok(array) => Some(array),
+        Err(()) => None,
+    }
+}
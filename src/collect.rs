@@ -0,0 +1,99 @@
+use crate::guard::DropGuard;
+use crate::{mark_initialized, uninit_buf};
+
+/// Drives `iter`, writing its first `N` items into a freshly built array
+///
+/// Every entry point that discards its progress on failure ([`crate::build_array`],
+/// [`crate::UnarrayArrayExt::map_result`], [`crate::ArrayFromIter`], ...) is expressed in terms
+/// of this function (or [`collect_into_array_unchecked`]), so their drop-guard and panic-safety
+/// logic lives in exactly one audited place. [`crate::array_from_iter`] is the one exception: it
+/// reports already-collected elements back to the caller instead of dropping them, so it drives
+/// its own `uninit_buf` directly
+///
+/// Returns `None` if `iter` yields fewer than `N` items before running out. The elements already
+/// written are dropped in that case, via [`DropGuard`], so nothing leaks
+pub(crate) fn collect_into_array<T, I, const N: usize>(iter: I) -> Option<[T; N]>
+where
+    I: Iterator<Item = T>,
+{
+    let mut iter = iter;
+    let mut buffer = uninit_buf::<T, N>();
+    let mut guard = DropGuard::new(&mut buffer);
+
+    for slot in &mut buffer {
+        match iter.next() {
+            Some(item) => {
+                slot.write(item);
+                guard.increment();
+            }
+            None => return None,
+        }
+    }
+
+    // SAFETY:
+    // The loop above returns early whenever `iter` runs out before every slot is written to. So
+    // reaching this point means every slot in `buffer` has been initialized
+    std::mem::forget(guard);
+    Some(unsafe { mark_initialized(buffer) })
+}
+
+/// Like [`collect_into_array`], but skips the length check, assuming `iter` yields **at least**
+/// `N` items. Useful when the caller can prove this statically, e.g. driving `(0..N).map(f)`
+///
+/// # Safety
+///
+/// `iter` must yield at least `N` items, otherwise this is undefined behaviour
+pub(crate) unsafe fn collect_into_array_unchecked<T, I, const N: usize>(iter: I) -> [T; N]
+where
+    I: Iterator<Item = T>,
+{
+    match collect_into_array(iter) {
+        Some(array) => array,
+        // SAFETY: the caller guarantees `iter` yields at least `N` items, so `collect_into_array`
+        // can never observe too few
+        None => std::hint::unreachable_unchecked(),
+    }
+}
+
+/// Adapts an `Iterator<Item = Result<T, E>>` into an `Iterator<Item = T>` that stops as soon as
+/// it sees an `Err`, stashing it in `error` so the caller can recover it afterwards
+///
+/// This lets [`try_collect_into_array`] short-circuit on the first error while still driving its
+/// array-building through the single [`collect_into_array`] core, instead of duplicating it
+struct ShortCircuit<I, E> {
+    iter: I,
+    error: Option<E>,
+}
+
+impl<T, E, I> Iterator for ShortCircuit<I, E>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self.iter.next() {
+            Some(Ok(item)) => Some(item),
+            Some(Err(e)) => {
+                self.error = Some(e);
+                None
+            }
+            None => None,
+        }
+    }
+}
+
+/// Drives `iter`, short-circuiting on the first `Err`. Reports `Ok(None)` if `iter` yields fewer
+/// than `N` `Ok` items before running out, analogous to [`collect_into_array`]'s `None`
+pub(crate) fn try_collect_into_array<T, E, I, const N: usize>(iter: I) -> Result<Option<[T; N]>, E>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    let mut shunt = ShortCircuit { iter, error: None };
+    let array = collect_into_array::<T, _, N>(&mut shunt);
+
+    match shunt.error {
+        Some(e) => Err(e),
+        None => Ok(array),
+    }
+}
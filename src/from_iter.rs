@@ -1,5 +1,7 @@
-use core::iter::FromIterator;
+use crate::collect::{collect_into_array, try_collect_into_array};
+use crate::guard::DropGuard;
 use crate::{mark_initialized, uninit_buf};
+use core::iter::FromIterator;
 
 /// A wrapper type to collect an [`Iterator`] into an array
 ///
@@ -27,28 +29,162 @@ pub struct ArrayFromIter<T, const N: usize>(pub Option<[T; N]>);
 
 impl<T, const N: usize> FromIterator<T> for ArrayFromIter<T, N> {
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
-        let mut buffer = uninit_buf::<T, N>();
         let mut iter = iter.into_iter();
-        let mut buf_iter = buffer.iter_mut();
-
-        loop {
-            let item = iter.next();
-            let slot = buf_iter.next();
-
-            match (item, slot) {
-                (Some(item), Some(slot)) => slot.write(item),
-                (Some(_), None) => return Self(None),
-                (None, Some(_)) => return Self(None),
-                // SAFETY
-                // If this is reached, every prior iteration of the loop has matched
-                // (Some(_), Some(_)). As such, both iterators have yielded the same number of
-                // elements, so every slot has been written to
-                (None, None) => return Self(Some(unsafe { mark_initialized(buffer) })),
-            };
+        let array = collect_into_array(&mut iter);
+
+        // `collect_into_array` only looks at the first `N` items, so check there isn't anything
+        // left over before declaring success
+        match array {
+            Some(array) if iter.next().is_none() => Self(Some(array)),
+            _ => Self(None),
+        }
+    }
+}
+
+/// A wrapper type to collect an [`Iterator`] of [`Result`]s into a single `Result` of an array
+///
+/// ```
+/// # use unarray::*;
+/// let iter = ["1", "2", "3"].into_iter().map(|s| s.parse::<i32>());
+/// let TryArrayFromIter(result) = iter.collect();
+///
+/// assert_eq!(result, Ok(Some([1, 2, 3])));
+/// ```
+/// This short-circuits on the first `Err`, analogous to how [`Result`] already implements
+/// [`FromIterator`] for collections such as `Vec`:
+/// ```
+/// # use unarray::*;
+/// let iter = ["1", "uh oh", "3"].into_iter().map(|s| s.parse::<i32>());
+/// let TryArrayFromIter::<i32, _, 3>(result) = iter.collect();
+///
+/// assert!(result.is_err());
+/// ```
+/// As with [`ArrayFromIter`], the iterator must yield **exactly** `N` elements for the collection
+/// to succeed. If no error is encountered, but the length doesn't match, the outer `Result` is
+/// `Ok`, with the length mismatch reported via the inner `Option`:
+/// ```
+/// # use unarray::*;
+/// let iter = ["1", "2"].into_iter().map(|s| s.parse::<i32>());
+/// let TryArrayFromIter::<i32, _, 3>(result) = iter.collect();
+///
+/// assert_eq!(result, Ok(None));
+/// ```
+pub struct TryArrayFromIter<T, E, const N: usize>(pub Result<Option<[T; N]>, E>);
+
+impl<T, E, const N: usize> FromIterator<Result<T, E>> for TryArrayFromIter<T, E, N> {
+    fn from_iter<I: IntoIterator<Item = Result<T, E>>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+
+        // `try_collect_into_array` only looks at the first `N` items, so check there isn't
+        // anything left over (another error, or a length mismatch) before declaring success
+        match try_collect_into_array(&mut iter) {
+            Ok(Some(array)) => match iter.next() {
+                None => Self(Ok(Some(array))),
+                Some(Ok(_)) => Self(Ok(None)),
+                Some(Err(e)) => Self(Err(e)),
+            },
+            Ok(None) => Self(Ok(None)),
+            Err(e) => Self(Err(e)),
         }
     }
 }
 
+/// The outcome of [`array_from_iter`]
+///
+/// Unlike [`ArrayFromIter`], which discards everything but a yes/no answer when the length
+/// doesn't match, this reports exactly what happened: how many elements were collected, or the
+/// array plus the rest of the iterator, so callers can keep draining it
+pub enum ArrayFromIterOutcome<T, I, const N: usize> {
+    /// The iterator yielded exactly `N` elements
+    Exact([T; N]),
+    /// The iterator yielded fewer than `N` elements. Contains every element that was collected
+    /// before the iterator ran out
+    TooFew {
+        /// The elements collected before the iterator was exhausted
+        collected: Vec<T>,
+    },
+    /// The iterator yielded more than `N` elements. Contains the first `N` elements as an array,
+    /// plus the rest of the iterator, not yet consumed
+    TooMany {
+        /// The first `N` elements, collected into an array
+        array: [T; N],
+        /// The rest of the iterator, positioned immediately after the `N`th element
+        extra: I,
+    },
+}
+
+/// Collect an iterator into an array, reporting a partial result instead of discarding it when
+/// the iterator doesn't yield **exactly** `N` elements
+///
+/// ```
+/// # use unarray::*;
+/// let array = array_from_iter::<_, _, 3>(vec![1, 2, 3]);
+/// assert!(matches!(array, ArrayFromIterOutcome::Exact([1, 2, 3])));
+/// ```
+/// If the iterator runs out early, the elements collected so far are returned, instead of being
+/// dropped:
+/// ```
+/// # use unarray::*;
+/// let ArrayFromIterOutcome::TooFew::<_, _, 3> { collected } = array_from_iter(vec![1, 2]) else {
+///   panic!("expected TooFew");
+/// };
+/// assert_eq!(collected, vec![1, 2]);
+/// ```
+/// If the iterator yields more than `N` elements, the first `N` are returned as an array, along
+/// with the rest of the iterator, so callers can peel off a fixed-size prefix and keep draining
+/// the remainder (e.g. to split a long iterator into `[T; N]`-sized chunks):
+/// ```
+/// # use unarray::*;
+/// let ArrayFromIterOutcome::TooMany::<_, _, 3> { array, extra } = array_from_iter(vec![1, 2, 3, 4, 5]) else {
+///   panic!("expected TooMany");
+/// };
+/// assert_eq!(array, [1, 2, 3]);
+/// assert_eq!(extra.collect::<Vec<_>>(), vec![4, 5]);
+/// ```
+pub fn array_from_iter<T, I, const N: usize>(
+    iter: I,
+) -> ArrayFromIterOutcome<T, std::iter::Peekable<I::IntoIter>, N>
+where
+    I: IntoIterator<Item = T>,
+{
+    let mut iter = iter.into_iter().peekable();
+    let mut buffer = uninit_buf::<T, N>();
+    let mut guard = DropGuard::new(&mut buffer);
+    let mut count = 0;
+
+    while count < N {
+        match iter.next() {
+            Some(item) => {
+                buffer[count].write(item);
+                guard.increment();
+                count += 1;
+            }
+            None => {
+                // SAFETY:
+                // `guard` has tracked exactly `count` initialized elements at the start of
+                // `buffer`, so reading each of them out by pointer is sound. Ownership of each
+                // element moves into `collected`, so `guard` must not also drop them, hence the
+                // `mem::forget` below
+                let ptr = buffer.as_mut_ptr().cast::<T>();
+                let collected = (0..count).map(|i| unsafe { ptr.add(i).read() }).collect();
+                std::mem::forget(guard);
+                return ArrayFromIterOutcome::TooFew { collected };
+            }
+        }
+    }
+
+    // SAFETY: the loop above only exits via `return` until `count == N`, at which point every
+    // slot in `buffer` has been written to
+    std::mem::forget(guard);
+    let array = unsafe { mark_initialized(buffer) };
+
+    if iter.peek().is_some() {
+        ArrayFromIterOutcome::TooMany { array, extra: iter }
+    } else {
+        ArrayFromIterOutcome::Exact(array)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -104,6 +240,58 @@ mod tests {
         let ArrayFromIter(array) = vec.into_iter().collect();
         prop_assert_eq!(array.unwrap(), expected);
     }
-}
 
+    #[test]
+    fn can_try_collect_array_from_iter() {
+        let iter = ["1", "2", "3"].into_iter().map(|s| s.parse::<i32>());
+
+        let TryArrayFromIter(result) = iter.collect();
+        assert_eq!(result.unwrap().unwrap(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn try_collect_short_circuits_on_first_error() {
+        let iter = ["1", "uh oh", "3"].into_iter().map(|s| s.parse::<i32>());
 
+        let TryArrayFromIter(result) = iter.collect();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn try_collect_fails_if_incorrect_number_of_elements() {
+        let iter = ["1", "2", "3"].into_iter().map(|s| s.parse::<i32>());
+        let TryArrayFromIter::<_, std::num::ParseIntError, 4>(result) = iter.collect();
+        assert_eq!(result.unwrap(), None);
+
+        let iter = ["1", "2", "3"].into_iter().map(|s| s.parse::<i32>());
+        let TryArrayFromIter::<_, std::num::ParseIntError, 2>(result) = iter.collect();
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[test]
+    fn array_from_iter_reports_exact() {
+        let result = array_from_iter::<_, _, 3>(vec![1, 2, 3]);
+        assert!(matches!(result, ArrayFromIterOutcome::Exact([1, 2, 3])));
+    }
+
+    #[test]
+    fn array_from_iter_reports_too_few() {
+        let result = array_from_iter::<_, _, 3>(vec![1, 2]);
+        match result {
+            ArrayFromIterOutcome::TooFew { collected } => assert_eq!(collected, vec![1, 2]),
+            _ => panic!("expected TooFew"),
+        }
+    }
+
+    #[test]
+    fn array_from_iter_reports_too_many_and_keeps_the_rest_of_the_iterator() {
+        let result = array_from_iter::<_, _, 3>(vec![1, 2, 3, 4, 5]);
+        match result {
+            ArrayFromIterOutcome::TooMany { array, extra } => {
+                assert_eq!(array, [1, 2, 3]);
+                assert_eq!(extra.collect::<Vec<_>>(), vec![4, 5]);
+            }
+            _ => panic!("expected TooMany"),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use std::mem::MaybeUninit;
+
+/// Guards the first `initialized` elements of a `[MaybeUninit<T>; N]` buffer, dropping them if
+/// the guard itself is dropped.
+///
+/// This is used by every entry point that writes into an `uninit_buf` one element at a time
+/// (e.g. [`crate::UnarrayArrayExt::map_result`], [`crate::ArrayFromIter`]). Call [`Self::increment`]
+/// immediately after each successful write, so that an early `return` or an unwinding panic runs
+/// the destructors of the elements written so far, instead of leaking them. Once the buffer is
+/// fully initialized, callers must [`std::mem::forget`] the guard, otherwise the finished array
+/// would be dropped twice.
+pub(crate) struct DropGuard<T> {
+    ptr: *mut T,
+    initialized: usize,
+}
+
+impl<T> DropGuard<T> {
+    /// Create a guard over `buf`, initially covering zero elements
+    pub(crate) fn new<const N: usize>(buf: &mut [MaybeUninit<T>; N]) -> Self {
+        Self {
+            ptr: buf.as_mut_ptr().cast(),
+            initialized: 0,
+        }
+    }
+
+    /// Record that one more element (immediately after the ones already covered) has been
+    /// initialized
+    pub(crate) fn increment(&mut self) {
+        self.initialized += 1;
+    }
+}
+
+impl<T> Drop for DropGuard<T> {
+    fn drop(&mut self) {
+        // SAFETY:
+        // `ptr` points to the start of the buffer passed to `new`, and `initialized` only ever
+        // counts up by one per call to `increment`, which callers only do immediately after
+        // writing that element. So `ptr` is valid for reads and writes for `initialized`
+        // contiguous, initialized `T`s, making this slice valid to drop in place.
+        unsafe {
+            let slice = std::ptr::slice_from_raw_parts_mut(self.ptr, self.initialized);
+            std::ptr::drop_in_place(slice);
+        }
+    }
+}
+
+/// Guards the not-yet-consumed suffix of a slice of `T`s that is being read out element by
+/// element (e.g. via [`std::ptr::read`]), dropping that suffix if the guard itself is dropped.
+///
+/// This is the mirror image of [`DropGuard`]: instead of growing to cover elements as they're
+/// written, it shrinks to exclude elements as they're read out. Call [`Self::advance`]
+/// immediately after each element is read, before handing it to caller code that might panic, so
+/// that an unwinding panic still drops the remaining, not-yet-read elements instead of leaking
+/// them.
+pub(crate) struct ConsumeGuard<T> {
+    ptr: *mut T,
+    remaining: usize,
+}
+
+impl<T> ConsumeGuard<T> {
+    /// Create a guard over the `remaining` elements starting at `ptr`
+    pub(crate) fn new(ptr: *mut T, remaining: usize) -> Self {
+        Self { ptr, remaining }
+    }
+
+    /// Record that the element at the front of the guarded range has been read out, excluding it
+    /// from the guard's responsibility
+    pub(crate) fn advance(&mut self) {
+        // SAFETY: `ptr` still has `remaining` elements ahead of it (every caller calls `advance`
+        // at most once per element the guard was created over), so it's valid to offset by one more
+        self.ptr = unsafe { self.ptr.add(1) };
+        self.remaining -= 1;
+    }
+}
+
+impl<T> Drop for ConsumeGuard<T> {
+    fn drop(&mut self) {
+        // SAFETY:
+        // `ptr` always points at the first not-yet-read element, and `remaining` tracks exactly
+        // how many elements are left from there, so this slice is valid to drop in place.
+        unsafe {
+            let slice = std::ptr::slice_from_raw_parts_mut(self.ptr, self.remaining);
+            std::ptr::drop_in_place(slice);
+        }
+    }
+}
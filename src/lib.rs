@@ -79,11 +79,15 @@
 use std::mem::MaybeUninit;
 
 mod build;
+mod collect;
+mod from_iter;
+mod guard;
 mod map;
 #[cfg(test)]
 mod tests;
 
 pub use build::{build_array, build_array_option, build_array_result};
+pub use from_iter::{array_from_iter, ArrayFromIter, ArrayFromIterOutcome, TryArrayFromIter};
 pub use map::UnarrayArrayExt;
 
 /// Convert a `[MaybeUninit<T>; N]` to a `[T; N]`
@@ -1,9 +1,13 @@
-use crate::{mark_initialized, uninit_buf};
+use std::mem::ManuallyDrop;
+
+use crate::collect::try_collect_into_array;
+use crate::guard::ConsumeGuard;
 
 /// An extension trait that adds methods to `[T; N]`
 ///
-/// This trait provides [`UnarrayArrayExt::map_result`] and [`UnarrayArrayExt::map_option`], 
-/// which provide functionality similar to the nightly-only [`array::try_map`]
+/// This trait provides [`UnarrayArrayExt::map_result`] and [`UnarrayArrayExt::map_option`],
+/// which provide functionality similar to the nightly-only [`array::try_map`], as well as
+/// [`UnarrayArrayExt::for_each_array`] for consuming an array by value
 pub trait UnarrayArrayExt<T, const N: usize> {
     /// Maps an array, short-circuiting if any element produces an `Err`
     ///
@@ -48,27 +52,34 @@ pub trait UnarrayArrayExt<T, const N: usize> {
     ///
     /// For functions that return an `Result`, consider using [`UnarrayArrayExt::map_result`]
     fn map_option<S>(self, f: impl FnMut(T) -> Option<S>) -> Option<[S; N]>;
+
+    /// Consumes the array by value, calling `f` with each owned element, in order
+    ///
+    /// ```
+    /// # use unarray::*;
+    /// let array = [String::from("a"), String::from("b"), String::from("c")];
+    /// let mut joined = String::new();
+    /// array.for_each_array(|s| joined.push_str(&s));
+    /// assert_eq!(joined, "abc");
+    /// ```
+    ///
+    /// This is similar to `IntoIterator::into_iter().for_each(f)`, but doesn't materialize a
+    /// by-value array iterator, which has to store the entire `[T; N]` inline. Instead, elements
+    /// are read out one at a time through a cursor no bigger than a slice iterator (two
+    /// pointers), regardless of `N`, which gives the optimizer a better shot at scalar-replacing
+    /// the array instead of shuffling all of it around up front
+    ///
+    /// This function does not allocate space on the heap
+    fn for_each_array(self, f: impl FnMut(T));
 }
 
 impl<T, const N: usize> UnarrayArrayExt<T, N> for [T; N] {
-    fn map_result<S, E>(self, mut f: impl FnMut(T) -> Result<S, E>) -> Result<[S; N], E> {
-        let mut result = uninit_buf();
-
-        // This is quaranteed to loop over every element (or panic), since both `result` and `self` have N elements
-        // If a panic occurs, uninitialized data is never dropped, since `MaybeUninit` wraps its
-        // contained data in `ManuallyDrop`
-        for (item, slot) in self.into_iter().zip(&mut result) {
-            match f(item) {
-                Ok(s) => slot.write(s),
-                Err(e) => return Err(e),
-            };
-        }
-
-        // SAFETY:
-        // At this point in execution, we have iterated over all elements of `result`. If any
-        // errors were encountered, we would have already returned. So it's safe to remove the
-        // MaybeUninit wrapper
-        Ok(unsafe { mark_initialized(result) })
+    fn map_result<S, E>(self, f: impl FnMut(T) -> Result<S, E>) -> Result<[S; N], E> {
+        try_collect_into_array(self.into_iter().map(f)).map(|array| {
+            // `self` has exactly `N` elements, so `try_collect_into_array` can never report too
+            // few
+            array.expect("self has exactly N elements")
+        })
     }
 
     fn map_option<S>(self, mut f: impl FnMut(T) -> Option<S>) -> Option<[S; N]> {
@@ -81,6 +92,26 @@ impl<T, const N: usize> UnarrayArrayExt<T, N> for [T; N] {
             Err(()) => None,
         }
     }
+
+    fn for_each_array(self, mut f: impl FnMut(T)) {
+        // `ManuallyDrop` stops `self`'s destructor running once every element has been read out
+        // of it below
+        let mut array = ManuallyDrop::new(self);
+        let mut guard = ConsumeGuard::new(array.as_mut_ptr(), N);
+
+        for slot in array.iter_mut() {
+            // SAFETY:
+            // `slot` is still within the range `guard` covers, so it hasn't been read out yet.
+            // `guard.advance()` immediately excludes it from that range, so if `f` panics, the
+            // guard won't also try to drop this (now moved-out-of) slot
+            let item = unsafe { std::ptr::read(slot) };
+            guard.advance();
+            f(item);
+        }
+
+        // every element has been read out, so `guard` now covers zero elements and dropping it
+        // here is a no-op
+    }
 }
 
 #[cfg(test)]
@@ -130,6 +161,25 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_for_each_array() {
+        let array = [1, 2, 3];
+        let mut seen = Vec::new();
+        array.for_each_array(|i| seen.push(i));
+        assert_eq!(seen, vec![1, 2, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_for_each_array_panic() {
+        let array = [1, 2, 3];
+        array.for_each_array(|i| {
+            if i > 2 {
+                panic!();
+            }
+        });
+    }
+
     const LEN: usize = 100;
 
     #[proptest]